@@ -7,78 +7,159 @@
 //! * ✅ Case insensitive
 //! * ✅ Backslashes are normalized to forward slashes
 //! * ✅ Trailing slashes are removed, except for root slash (for absolute POSIX paths)
-//! * ✅ Repeating slashes are normalized to a single slash
+//! * ✅ Repeating slashes are normalized to a single slash, except a leading `//` (or more), which is parsed as a UNC prefix instead and kept distinct from a single-slash POSIX root - see below
+//! * ✅ `.` and `..` segments are resolved lexically (no filesystem access)
+//! * ✅ `components()`, `starts_with()`, `ends_with()` and `strip_prefix()` match at component boundaries, not substrings
+//! * ✅ Windows prefixes (verbatim `\\?\`, device `\\.\`, UNC, drive letters) are parsed and canonicalized, e.g. `\\?\C:\foo` compares equal to `C:\foo`
 //! * ❌ Comparing a Windows path with a POSIX path will not work if either is absolute (Windows paths with a drive letter, POSIX paths with a preceeding slash)
 //! * ❌ Comparing a Windows UNC path will not work with any POSIX path
 //! * ❌ POSIX paths can contain backslashes in file names, but Windows paths cannot - these will be normalized to forward slashes and you will lose that information
+//! * ❌ A POSIX path with a leading `//` (or more repeated slashes) will not compare equal to the same path with a single leading `/`, since the leading repeat is parsed as a UNC prefix
 
-use std::{borrow::Borrow, path::PathBuf, str::FromStr};
+use bstr::{BString, ByteSlice};
+use std::{
+	borrow::{Borrow, Cow},
+	ffi::OsStr,
+	ops::Deref,
+	path::{Path, PathBuf},
+	str::{FromStr, Utf8Error},
+};
 
 /// A lossy representation of a path using a `String` for quick and dirty fuzzy comparison.
 ///
 /// This type deliberately does not implement `PartialEq` for any other types. You should only compare `FuzzyPath` with another `FuzzyPath`, as the normalization must take place.
 ///
+/// Internally this is backed by [`BString`], not `String`, so paths that aren't valid UTF-8 (common
+/// on Unix, and WTF-8 lone surrogates on Windows) normalize and round-trip losslessly. Use
+/// [`FuzzyPath::as_str_lossy`] when you want a UTF-8 `str` view, or [`FuzzyPath::as_bytes`]/
+/// [`FuzzyPath::into_bytes`] when you want the raw bytes. `AsRef<String>`/`Borrow<String>` are not
+/// provided, since a `&String` can't be handed back from byte-backed storage without owning one.
+/// `Borrow<str>` is not provided either, even though `AsRef<str>` is: `HashMap::get` calls `borrow()`
+/// on every candidate key that shares a hash bucket with the query, not just ones equal to it, so a
+/// panicking `Borrow<str>` could panic on an unrelated, perfectly valid entry. Use [`FuzzyStr`] (via
+/// `Borrow<FuzzyStr>`) for panic-free `HashMap` lookups by an already-normalized key instead.
+///
 /// # Comparison rules
 ///
 /// * ✅ Case insensitive
 /// * ✅ Backslashes are normalized to forward slashes
 /// * ✅ Trailing slashes are removed, except for root slash (for absolute POSIX paths)
-/// * ✅ Repeating slashes are normalized to a single slash
+/// * ✅ Repeating slashes are normalized to a single slash, except a leading `//` (or more), which is parsed as a UNC prefix instead and kept distinct from a single-slash POSIX root - see below
+/// * ✅ `.` and `..` segments are resolved lexically (no filesystem access)
+/// * ✅ `components()`, `starts_with()`, `ends_with()` and `strip_prefix()` match at component boundaries, not substrings
+/// * ✅ Windows prefixes (verbatim `\\?\`, device `\\.\`, UNC, drive letters) are parsed and canonicalized, e.g. `\\?\C:\foo` compares equal to `C:\foo`
 /// * ❌ Comparing a Windows path with a POSIX path will not work if either is absolute (Windows paths with a drive letter, POSIX paths with a preceeding slash)
 /// * ❌ Comparing a Windows UNC path will not work with any POSIX path
 /// * ❌ POSIX paths can contain backslashes in file names, but Windows paths cannot - these will be normalized to forward slashes and you will lose that information
-#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
-#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
-pub struct FuzzyPath(String);
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct FuzzyPath(BString);
+
+// Default is implemented by hand, rather than derived, since deriving it would produce an empty
+// `BString` - but an empty path normalizes to `"."` (see `resolve_dot_segments`), so a derived
+// `Default` would violate the invariant that every `FuzzyPath` is normalized.
+impl Default for FuzzyPath {
+	fn default() -> Self {
+		FuzzyPath::from("")
+	}
+}
+
+// Hash is implemented by hand, rather than derived, to match `str`'s hashing (bytes followed by a
+// `0xff` terminator byte) instead of `BString`'s derived slice hashing (a length-prefixed hash) -
+// otherwise looking a `FuzzyPath` up in a `HashMap` by `&FuzzyStr` via `Borrow<FuzzyStr>` would hash
+// to the wrong bucket.
+impl std::hash::Hash for FuzzyPath {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		state.write(self.0.as_slice());
+		state.write_u8(0xff);
+	}
+}
+
+/// The kind of filesystem-root prefix detected at the start of a [`FuzzyPath`], if any.
+///
+/// Comparing two `FuzzyPath`s with different prefixes (or a prefixed path against a plain POSIX-style
+/// one) is not meaningful, since they don't refer to the same namespace; use [`FuzzyStr::prefix`] to
+/// check before relying on equality across platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FuzzyPathPrefix {
+	/// No recognized root prefix - a plain POSIX-style absolute or relative path.
+	None,
+	/// A Windows drive letter root, e.g. `c:` in `c:/foo`. The drive letter is always lowercase.
+	Drive(char),
+	/// A Windows UNC path, e.g. `//server/share`.
+	Unc,
+	/// A Windows device path, e.g. `//./pipe/foo`.
+	Device,
+}
+
 impl FuzzyPath {
 	/// It is a logic error to construct a `FuzzyPath` from a string that is not correctly normalized.
 	///
 	/// To see the normalization implementation, see the `From<&str>` implementation for `FuzzyPath`
-	pub unsafe fn from_str_unchecked<S: Into<String>>(str: S) -> Self {
-		FuzzyPath(str.into())
+	///
+	/// # Safety
+	///
+	/// This isn't memory-unsafe to call with un-normalized input, but doing so breaks the invariant
+	/// that every `FuzzyPath` is normalized, which other methods (e.g. comparisons, `components()`)
+	/// rely on to behave correctly.
+	pub unsafe fn from_str_unchecked<S: Into<Vec<u8>>>(str: S) -> Self {
+		FuzzyPath(BString::from(str.into()))
 	}
 
-	/// Returns the underlying normalized `String` of the path.
+	/// Returns the underlying normalized path as a `String`, lossily replacing any invalid UTF-8 with `U+FFFD`.
 	///
 	/// This is lossy because not all paths are UTF-8 and it has been normalized.
 	pub fn into_string_lossy(self) -> String {
-		self.0
+		self.0.to_str_lossy().into_owned()
 	}
 
-	/// Returns the underlying normalized `String` of the path as a `&str`
+	/// Returns the underlying normalized path as a `Cow<'_, str>`, lossily replacing any invalid UTF-8 with `U+FFFD`.
 	///
 	/// This is lossy because not all paths are UTF-8 and it has been normalized.
-	pub fn as_str_lossy(&self) -> &str {
-		self.0.as_str()
+	pub fn as_str_lossy(&self) -> Cow<'_, str> {
+		self.0.to_str_lossy()
+	}
+
+	/// Returns the underlying normalized path as raw bytes, with no information lost.
+	pub fn as_bytes(&self) -> &[u8] {
+		self.0.as_slice()
 	}
+
+	/// Consumes the `FuzzyPath`, returning the underlying normalized path as raw bytes, with no information lost.
+	pub fn into_bytes(self) -> Vec<u8> {
+		self.0.into()
+	}
+
+	/// Like the `From` impls that accept a path (`&OsStr`, `&Path`, `PathBuf`, ...), but fails instead
+	/// of losing information if the path is not valid UTF-8.
+	///
+	/// This can't be a `TryFrom` impl: it would conflict with the standard library's blanket
+	/// `impl<T, U> TryFrom<U> for T where U: Into<T>`, which already applies here because of the
+	/// infallible `From` impls.
+	pub fn from_utf8(path: impl AsRef<OsStr>) -> Result<Self, Utf8Error> {
+		let normalized = normalize(&os_str_to_bytes(path.as_ref()));
+		std::str::from_utf8(normalized.as_slice())?;
+		Ok(FuzzyPath(normalized))
+	}
+
 }
 impl From<&str> for FuzzyPath {
 	fn from(str: &str) -> Self {
-		let str = str.replace("\\", "/"); // Normalize backslashes to forward slashes
-		let str = str.trim_end_matches("/"); // Trim trailing slashes
-
-		// Find and obliterate repeating slashes
-		let mut normalized = String::with_capacity(str.len());
-		let mut slash = false;
-		for char in str.chars() {
-			if char == '/' {
-				if !slash {
-					slash = true;
-					normalized.push('/');
-				}
-			} else {
-				slash = false;
-				let char = char.to_lowercase(); // Normalize to lowercase
-				normalized.extend(char);
-			}
-		}
-
-		FuzzyPath(normalized)
+		FuzzyPath(normalize(str.as_bytes()))
+	}
+}
+impl From<&OsStr> for FuzzyPath {
+	fn from(os_str: &OsStr) -> Self {
+		FuzzyPath(normalize(&os_str_to_bytes(os_str)))
+	}
+}
+impl From<&Path> for FuzzyPath {
+	fn from(path: &Path) -> Self {
+		path.as_os_str().into()
 	}
 }
 impl From<PathBuf> for FuzzyPath {
 	fn from(pathbuf: PathBuf) -> Self {
-		pathbuf.to_string_lossy().as_ref().into()
+		pathbuf.as_path().into()
 	}
 }
 impl From<String> for FuzzyPath {
@@ -88,32 +169,31 @@ impl From<String> for FuzzyPath {
 }
 impl From<FuzzyPath> for PathBuf {
 	fn from(fuzzy: FuzzyPath) -> Self {
-		PathBuf::from(fuzzy.0)
+		PathBuf::from(fuzzy.into_string_lossy())
 	}
 }
 impl From<FuzzyPath> for String {
 	fn from(fuzzy: FuzzyPath) -> Self {
-		fuzzy.0
+		fuzzy.into_string_lossy()
 	}
 }
-impl AsRef<String> for FuzzyPath {
-	fn as_ref(&self) -> &String {
-		&self.0
+impl AsRef<[u8]> for FuzzyPath {
+	fn as_ref(&self) -> &[u8] {
+		self.0.as_slice()
 	}
 }
 impl AsRef<str> for FuzzyPath {
+	/// # Panics
+	///
+	/// Panics if the path is not valid UTF-8. Use [`FuzzyPath::as_str_lossy`] if the path may contain
+	/// non-UTF-8 bytes.
 	fn as_ref(&self) -> &str {
-		self.0.as_str()
+		self.0.to_str().expect("FuzzyPath is not valid UTF-8, use as_str_lossy instead")
 	}
 }
-impl Borrow<str> for FuzzyPath {
-	fn borrow(&self) -> &str {
-		self.0.as_str()
-	}
-}
-impl Borrow<String> for FuzzyPath {
-	fn borrow(&self) -> &String {
-		&self.0
+impl std::fmt::Display for FuzzyPath {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		std::fmt::Display::fmt(&self.as_str_lossy(), f)
 	}
 }
 impl FromStr for FuzzyPath {
@@ -123,17 +203,551 @@ impl FromStr for FuzzyPath {
 		Ok(s.into())
 	}
 }
-impl ToString for FuzzyPath {
-	fn to_string(&self) -> String {
-		self.0.to_string()
+impl Deref for FuzzyPath {
+	type Target = FuzzyStr;
+
+	fn deref(&self) -> &FuzzyStr {
+		// SAFETY: `self.0` was normalized by the `normalize` function that produced this `FuzzyPath`.
+		unsafe { FuzzyStr::from_normalized_unchecked(self.0.as_slice()) }
+	}
+}
+impl Borrow<FuzzyStr> for FuzzyPath {
+	fn borrow(&self) -> &FuzzyStr {
+		self
+	}
+}
+impl AsRef<FuzzyStr> for FuzzyPath {
+	fn as_ref(&self) -> &FuzzyStr {
+		self
+	}
+}
+impl From<&FuzzyStr> for FuzzyPath {
+	fn from(fuzzy_str: &FuzzyStr) -> Self {
+		fuzzy_str.to_owned()
+	}
+}
+
+/// A borrowed, unsized counterpart to [`FuzzyPath`], analogous to how [`str`] relates to [`String`].
+///
+/// This lets you compare against or store a normalized path without allocating a new `FuzzyPath`,
+/// for example when looking a key up in a `HashMap<FuzzyPath, _>` by an already-normalized `&FuzzyStr`.
+///
+/// As with `FuzzyPath`, this type deliberately does not implement `PartialEq` for any other types.
+#[repr(transparent)]
+pub struct FuzzyStr([u8]);
+impl FuzzyStr {
+	/// It is a logic error to construct a `FuzzyStr` from bytes that are not correctly normalized.
+	///
+	/// To see the normalization implementation, see the `From<&str>` implementation for `FuzzyPath`
+	///
+	/// # Safety
+	///
+	/// This isn't memory-unsafe to call with un-normalized bytes, but doing so breaks the invariant
+	/// that every `FuzzyStr` is normalized, which other methods (e.g. comparisons, `components()`)
+	/// rely on to behave correctly.
+	pub unsafe fn from_normalized_unchecked(bytes: &[u8]) -> &FuzzyStr {
+		// SAFETY: `FuzzyStr` is `#[repr(transparent)]` over `[u8]`.
+		&*(bytes as *const [u8] as *const FuzzyStr)
+	}
+
+	/// Returns the underlying normalized path as raw bytes, with no information lost.
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.0
+	}
+
+	/// Returns the underlying normalized path as a `Cow<'_, str>`, lossily replacing any invalid UTF-8 with `U+FFFD`.
+	pub fn as_str_lossy(&self) -> Cow<'_, str> {
+		self.0.to_str_lossy()
+	}
+
+	/// Returns the kind of filesystem-root prefix at the start of this path, if any.
+	///
+	/// See [`FuzzyPathPrefix`] for why this matters before comparing paths across platforms.
+	pub fn prefix(&self) -> FuzzyPathPrefix {
+		let bytes = &self.0;
+		if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+			return FuzzyPathPrefix::Drive(bytes[0] as char);
+		}
+		// A device prefix is `//.` followed by a separator - a UNC path whose server name itself
+		// starts with `.` (e.g. `//.hiddenserver/share`) must still be classified as `Unc`, matching
+		// `parse_windows_prefix`.
+		if bytes.starts_with(b"//.") && bytes.get(3) == Some(&b'/') {
+			return FuzzyPathPrefix::Device;
+		}
+		if bytes.starts_with(b"//") {
+			return FuzzyPathPrefix::Unc;
+		}
+		FuzzyPathPrefix::None
+	}
+
+	/// Returns an iterator over the normalized `/`-separated components of this path.
+	///
+	/// A component that is not valid UTF-8 is yielded as a single `U+FFFD` replacement character in
+	/// its place.
+	pub fn components(&self) -> impl Iterator<Item = &str> + '_ {
+		self.0
+			.split(|&byte| byte == b'/')
+			.filter(|component| !component.is_empty())
+			.map(|component| component.to_str().unwrap_or("\u{fffd}"))
+	}
+
+	/// Like `components()`, but yields each component's raw bytes, so components that are not valid
+	/// UTF-8 are compared and kept exactly.
+	fn byte_components(&self) -> impl Iterator<Item = &[u8]> + '_ {
+		self.0.split(|&byte| byte == b'/').filter(|component| !component.is_empty())
+	}
+
+	/// Returns this path's root: the [`FuzzyPathPrefix`] it starts with (if any), and whether it is
+	/// absolute (a drive-relative path such as `c:foo` is not). Components drop the leading root slash
+	/// and a UNC prefix's `//`, so `starts_with`, `ends_with` and `strip_prefix` compare roots
+	/// separately via this before comparing components, to avoid e.g. an absolute path matching a
+	/// relative pattern.
+	fn root(&self) -> (FuzzyPathPrefix, bool) {
+		let prefix = self.prefix();
+		let is_absolute = match prefix {
+			FuzzyPathPrefix::None => self.0.first() == Some(&b'/'),
+			FuzzyPathPrefix::Drive(_) => self.0.get(2) == Some(&b'/'),
+			FuzzyPathPrefix::Unc | FuzzyPathPrefix::Device => true,
+		};
+		(prefix, is_absolute)
+	}
+
+	/// Returns `true` if `self` begins with `prefix`, matched at component boundaries rather than
+	/// as a raw substring, so `foo/bar` does not match a prefix of `foo/barbaz`. `self` and `prefix`
+	/// must also share the same root (see [`FuzzyStr::prefix`]) - for example an absolute path never
+	/// starts with a relative one, and vice versa.
+	pub fn starts_with(&self, prefix: &FuzzyStr) -> bool {
+		if self.root() != prefix.root() {
+			return false;
+		}
+		let mut self_components = self.byte_components();
+		let mut prefix_components = prefix.byte_components();
+		loop {
+			match (prefix_components.next(), self_components.next()) {
+				(None, _) => return true,
+				(Some(_), None) => return false,
+				(Some(a), Some(b)) if a != b => return false,
+				_ => continue,
+			}
+		}
+	}
+
+	/// Returns `true` if `self` ends with `suffix`, matched at component boundaries rather than as
+	/// a raw substring, so `foo/bar` does not match a suffix of `baz/foobar`. `self` and `suffix`
+	/// must also share the same root (see [`FuzzyStr::prefix`]) - for example `/etc` does not end
+	/// with the relative path `etc`.
+	pub fn ends_with(&self, suffix: &FuzzyStr) -> bool {
+		if self.root() != suffix.root() {
+			return false;
+		}
+		let self_components: Vec<&[u8]> = self.byte_components().collect();
+		let suffix_components: Vec<&[u8]> = suffix.byte_components().collect();
+		suffix_components.len() <= self_components.len() && self_components[self_components.len() - suffix_components.len()..] == suffix_components[..]
+	}
+
+	/// Strips `prefix` from `self` at component boundaries, returning `None` if `self` does not
+	/// start with `prefix`.
+	pub fn strip_prefix(&self, prefix: &FuzzyStr) -> Option<FuzzyPath> {
+		if !self.starts_with(prefix) {
+			return None;
+		}
+		let remainder = self.byte_components().skip(prefix.byte_components().count()).collect::<Vec<_>>().join(&b'/');
+		Some(if remainder.is_empty() { FuzzyPath(BString::from(".")) } else { FuzzyPath(BString::from(remainder)) })
+	}
+}
+impl PartialEq for FuzzyStr {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+impl Eq for FuzzyStr {}
+impl PartialOrd for FuzzyStr {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl Ord for FuzzyStr {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		self.0.cmp(&other.0)
+	}
+}
+impl std::hash::Hash for FuzzyStr {
+	// Matches `FuzzyPath`'s hand-written `Hash` impl (bytes + `0xff` terminator), so that `FuzzyStr`
+	// and an owned `FuzzyPath` with the same bytes always hash the same - required by `Borrow<FuzzyStr>`.
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		state.write(&self.0);
+		state.write_u8(0xff);
+	}
+}
+impl std::fmt::Debug for FuzzyStr {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		std::fmt::Debug::fmt(self.0.as_bstr(), f)
+	}
+}
+impl AsRef<[u8]> for FuzzyStr {
+	fn as_ref(&self) -> &[u8] {
+		&self.0
+	}
+}
+impl ToOwned for FuzzyStr {
+	type Owned = FuzzyPath;
+
+	fn to_owned(&self) -> FuzzyPath {
+		FuzzyPath(BString::from(self.0.to_vec()))
+	}
+}
+
+/// Converts an `OsStr` to its raw bytes, losslessly.
+///
+/// On Unix this is a zero-cost reinterpretation of the existing bytes. On Windows, `OsStr` is
+/// WTF-16 internally, so we re-encode it as WTF-8 (UTF-8 that also allows lone surrogates), which
+/// is exactly what lets us round-trip paths that aren't valid Unicode (e.g. lone surrogates from a
+/// lossy `\\?\` rename) without losing information.
+#[cfg(unix)]
+fn os_str_to_bytes(os_str: &OsStr) -> Cow<'_, [u8]> {
+	use std::os::unix::ffi::OsStrExt;
+	Cow::Borrowed(os_str.as_bytes())
+}
+
+#[cfg(windows)]
+fn os_str_to_bytes(os_str: &OsStr) -> Cow<'_, [u8]> {
+	use std::os::windows::ffi::OsStrExt;
+
+	let mut bytes = Vec::with_capacity(os_str.len());
+	let mut units = os_str.encode_wide().peekable();
+	while let Some(unit) = units.next() {
+		let code_point = match unit {
+			0xd800..=0xdbff => match units.peek().copied() {
+				Some(low @ 0xdc00..=0xdfff) => {
+					units.next();
+					0x10000 + ((unit as u32 - 0xd800) << 10) + (low as u32 - 0xdc00)
+				}
+				_ => {
+					push_wtf8_surrogate(&mut bytes, unit);
+					continue;
+				}
+			},
+			0xdc00..=0xdfff => {
+				push_wtf8_surrogate(&mut bytes, unit);
+				continue;
+			}
+			_ => unit as u32,
+		};
+		if let Some(char) = char::from_u32(code_point) {
+			let mut buf = [0u8; 4];
+			bytes.extend_from_slice(char.encode_utf8(&mut buf).as_bytes());
+		}
+	}
+	Cow::Owned(bytes)
+}
+
+/// Encodes a lone UTF-16 surrogate as the (non-standard, but well-defined by WTF-8) 3-byte sequence for its code point.
+#[cfg(windows)]
+fn push_wtf8_surrogate(bytes: &mut Vec<u8>, surrogate: u16) {
+	let code_point = surrogate as u32;
+	bytes.push(0xe0 | (code_point >> 12) as u8);
+	bytes.push(0x80 | ((code_point >> 6) & 0x3f) as u8);
+	bytes.push(0x80 | (code_point & 0x3f) as u8);
+}
+
+#[cfg(not(any(unix, windows)))]
+fn os_str_to_bytes(os_str: &OsStr) -> Cow<'_, [u8]> {
+	match os_str.to_str() {
+		Some(str) => Cow::Borrowed(str.as_bytes()),
+		None => Cow::Owned(os_str.to_string_lossy().into_owned().into_bytes()),
+	}
+}
+
+/// Normalizes raw path bytes: a Windows prefix (verbatim `\\?\`, device `\\.\`, UNC, or drive letter)
+/// is parsed and canonicalized first, then the remainder has backslashes turned into forward slashes,
+/// repeating slashes collapsed to one, trailing slashes trimmed, valid UTF-8 text case-folded to
+/// lowercase, and `.`/`..` segments resolved lexically (no filesystem access). Bytes that aren't part
+/// of a valid UTF-8 sequence (including invalid UTF-8 and WTF-8 surrogate encodings) are passed
+/// through untouched rather than lowercased, so normalization never loses data.
+fn normalize(bytes: &[u8]) -> BString {
+	let (prefix, rest) = parse_windows_prefix(bytes);
+	// A drive letter without a following separator is drive-relative (`C:foo`, relative to drive C's
+	// current directory), not absolute (`C:\foo`) - so it's treated the same as the no-prefix case.
+	// UNC and device prefixes are always absolute: there's no such thing as a drive-relative UNC path.
+	let is_absolute = match prefix {
+		FuzzyPathPrefix::None | FuzzyPathPrefix::Drive(_) => rest.first().map(|&byte| is_windows_separator(byte)).unwrap_or(false),
+		FuzzyPathPrefix::Unc | FuzzyPathPrefix::Device => true,
+	};
+
+	let mut collapsed: Vec<u8> = rest.iter().map(|&byte| if byte == b'\\' { b'/' } else { byte }).collect();
+	while collapsed.last() == Some(&b'/') {
+		collapsed.pop();
+	}
+
+	let mut deduped = Vec::with_capacity(collapsed.len());
+	let mut slash = false;
+	for byte in collapsed {
+		if byte == b'/' {
+			if !slash {
+				slash = true;
+				deduped.push(b'/');
+			}
+		} else {
+			slash = false;
+			deduped.push(byte);
+		}
+	}
+
+	let lowered = lowercase_preserving_invalid_utf8(&deduped);
+	let body = resolve_dot_segments(&lowered, is_absolute);
+
+	let mut normalized = Vec::with_capacity(body.len() + 4);
+	match prefix {
+		FuzzyPathPrefix::None => {
+			if is_absolute {
+				normalized.push(b'/');
+			}
+		}
+		FuzzyPathPrefix::Drive(drive) => {
+			normalized.push(drive as u8);
+			normalized.push(b':');
+			if is_absolute {
+				normalized.push(b'/');
+			}
+		}
+		FuzzyPathPrefix::Unc => normalized.extend_from_slice(b"//"),
+		FuzzyPathPrefix::Device => normalized.extend_from_slice(b"//./"),
+	}
+	normalized.extend_from_slice(&body);
+
+	BString::from(normalized)
+}
+
+/// Lowercases every valid UTF-8 char in `bytes` (Unicode lowercase mapping via `char::to_lowercase`,
+/// not just ASCII), leaving any bytes that aren't part of a valid UTF-8 sequence untouched so invalid
+/// UTF-8 and WTF-8 surrogate encodings still round-trip losslessly.
+fn lowercase_preserving_invalid_utf8(bytes: &[u8]) -> Vec<u8> {
+	let mut lowered = Vec::with_capacity(bytes.len());
+	let mut buf = [0u8; 4];
+	for (start, end, char) in bytes.char_indices() {
+		// An invalid sequence (1 to 3 bytes) decodes as U+FFFD, so its length alone can't tell it
+		// apart from a literal U+FFFD; copying the original bytes is correct for both.
+		if char == '\u{fffd}' {
+			lowered.extend_from_slice(&bytes[start..end]);
+		} else {
+			for lower in char.to_lowercase() {
+				lowered.extend_from_slice(lower.encode_utf8(&mut buf).as_bytes());
+			}
+		}
+	}
+	lowered
+}
+
+/// Returns `true` if `byte` is a Windows path separator (`/` or `\`).
+fn is_windows_separator(byte: u8) -> bool {
+	byte == b'/' || byte == b'\\'
+}
+
+/// Parses a Windows path prefix from the start of `bytes`, returning the prefix kind (if any) and
+/// the remainder of the path after it. Must run before slash-collapsing, since e.g. a UNC path's
+/// leading `//` has to be told apart from (and kept distinct from) a plain POSIX root's single `/`.
+///
+/// Recognizes verbatim prefixes (`\\?\foo`, `\\?\UNC\server\share`), device paths (`\\.\pipe\foo`),
+/// UNC paths (`\\server\share`), and drive letter roots (`C:\foo`).
+fn parse_windows_prefix(bytes: &[u8]) -> (FuzzyPathPrefix, &[u8]) {
+	if bytes.len() >= 4 && is_windows_separator(bytes[0]) && is_windows_separator(bytes[1]) && bytes[2] == b'?' && is_windows_separator(bytes[3]) {
+		// Verbatim `\\?\` prefix - strip it, then parse what follows as UNC or a drive letter.
+		let rest = &bytes[4..];
+		if let Some(after_unc_marker) = strip_literal_prefix(rest, b"UNC") {
+			if after_unc_marker.first().map(|&byte| is_windows_separator(byte)).unwrap_or(false) {
+				return (FuzzyPathPrefix::Unc, &after_unc_marker[1..]);
+			}
+		}
+		return parse_drive_or_none(rest);
+	}
+
+	if bytes.len() >= 4 && is_windows_separator(bytes[0]) && is_windows_separator(bytes[1]) && bytes[2] == b'.' && is_windows_separator(bytes[3]) {
+		return (FuzzyPathPrefix::Device, &bytes[4..]);
+	}
+
+	if bytes.len() >= 2 && is_windows_separator(bytes[0]) && is_windows_separator(bytes[1]) {
+		return (FuzzyPathPrefix::Unc, &bytes[2..]);
+	}
+
+	parse_drive_or_none(bytes)
+}
+
+/// Parses a `C:`-style drive letter root from the start of `bytes`, lowercasing the drive letter so
+/// `C:` and `c:` unify. Returns `FuzzyPathPrefix::None` if there isn't one.
+fn parse_drive_or_none(bytes: &[u8]) -> (FuzzyPathPrefix, &[u8]) {
+	if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+		return (FuzzyPathPrefix::Drive((bytes[0] as char).to_ascii_lowercase()), &bytes[2..]);
+	}
+	(FuzzyPathPrefix::None, bytes)
+}
+
+/// Strips `literal` (matched case-insensitively) from the start of `bytes`, if present.
+fn strip_literal_prefix<'a>(bytes: &'a [u8], literal: &[u8]) -> Option<&'a [u8]> {
+	if bytes.len() >= literal.len() && bytes[..literal.len()].eq_ignore_ascii_case(literal) {
+		Some(&bytes[literal.len()..])
+	} else {
+		None
 	}
 }
 
+/// Lexically resolves `.` and `..` components in an already slash-collapsed, prefix-stripped path,
+/// without touching the filesystem. `..` pops the previous component unless doing so would escape
+/// an absolute root (in which case it's dropped) or the stack only contains other `..` components
+/// (in which case it's kept, so relative paths like `../../x` survive).
+fn resolve_dot_segments(bytes: &[u8], is_absolute: bool) -> Vec<u8> {
+	let mut stack: Vec<&[u8]> = Vec::new();
+	for component in bytes.split(|&byte| byte == b'/') {
+		match component {
+			b"" | b"." => continue,
+			b".." => match stack.last() {
+				Some(&b"..") => stack.push(component),
+				Some(_) => {
+					stack.pop();
+				}
+				None if !is_absolute => stack.push(component),
+				None => {} // popping would escape the absolute root; drop it
+			},
+			_ => stack.push(component),
+		}
+	}
+
+	let mut resolved = Vec::with_capacity(bytes.len());
+	for (index, component) in stack.iter().enumerate() {
+		if index > 0 {
+			resolved.push(b'/');
+		}
+		resolved.extend_from_slice(component);
+	}
+	if resolved.is_empty() && !is_absolute {
+		resolved.push(b'.');
+	}
+
+	resolved
+}
+
 #[cfg(feature = "serde")]
 mod serde;
 
 #[test]
 fn test_normalize() {
 	assert_eq!(FuzzyPath::from("HELLO\\\\world/////foo/bar/////////"), FuzzyPath::from("hello/world/foo/bar"));
-	assert_eq!(FuzzyPath::from("\\\\HELLO\\\\world/////foo/bar/////////"), FuzzyPath::from("/hello/world/foo/bar"));
-}
\ No newline at end of file
+	// A leading double slash/backslash is now parsed as a UNC prefix (see `test_windows_prefixes`),
+	// so it's kept distinct from - and no longer collapses down to - a plain POSIX root slash.
+	assert_eq!(FuzzyPath::from("\\\\HELLO\\\\world/////foo/bar/////////"), FuzzyPath::from("//hello/world/foo/bar"));
+}
+
+#[test]
+fn test_unicode_case_folding() {
+	// Case-insensitivity covers Unicode lowercasing, not just ASCII.
+	assert_eq!(FuzzyPath::from("É/foo"), FuzzyPath::from("é/foo"));
+}
+
+#[test]
+fn test_dot_segments() {
+	assert_eq!(FuzzyPath::from("a/b/../c"), FuzzyPath::from("a/c"));
+	assert_eq!(FuzzyPath::from("./a/./b"), FuzzyPath::from("a/b"));
+	assert_eq!(FuzzyPath::from("../../x"), FuzzyPath::from("../../x"));
+	assert_eq!(FuzzyPath::from("/a/../../b"), FuzzyPath::from("/b"));
+	assert_eq!(FuzzyPath::from("a/.."), FuzzyPath::from("."));
+	assert_eq!(FuzzyPath::from("/"), FuzzyPath::from("/"));
+}
+
+#[test]
+fn test_default_is_normalized() {
+	assert_eq!(FuzzyPath::default(), FuzzyPath::from(""));
+	assert_eq!(FuzzyPath::default().components().collect::<Vec<_>>(), vec!["."]);
+}
+
+#[test]
+fn test_components() {
+	assert_eq!(FuzzyPath::from("/foo/bar/baz").components().collect::<Vec<_>>(), vec!["foo", "bar", "baz"]);
+	// An empty relative path normalizes to `.` (see `test_dot_segments`), which is itself one component.
+	assert_eq!(FuzzyPath::from("").components().collect::<Vec<_>>(), vec!["."]);
+}
+
+#[test]
+fn test_component_boundary_matching() {
+	assert!(FuzzyPath::from("foo/bar").starts_with(&FuzzyPath::from("foo")));
+	assert!(!FuzzyPath::from("foo/barbaz").starts_with(&FuzzyPath::from("foo/bar")));
+	assert!(FuzzyPath::from("foo/bar").ends_with(&FuzzyPath::from("bar")));
+	assert!(!FuzzyPath::from("baz/foobar").ends_with(&FuzzyPath::from("foo/bar")));
+	assert_eq!(FuzzyPath::from("foo/bar/baz").strip_prefix(&FuzzyPath::from("foo/bar")), Some(FuzzyPath::from("baz")));
+	assert_eq!(FuzzyPath::from("foo/bar").strip_prefix(&FuzzyPath::from("baz")), None);
+}
+
+#[test]
+fn test_root_must_match_for_component_boundary_matching() {
+	assert!(!FuzzyPath::from("/foo/bar").starts_with(&FuzzyPath::from("foo")));
+	assert!(!FuzzyPath::from("foo/bar").starts_with(&FuzzyPath::from("/foo")));
+	assert!(!FuzzyPath::from("/etc").ends_with(&FuzzyPath::from("etc")));
+	assert_eq!(FuzzyPath::from("/foo/bar").strip_prefix(&FuzzyPath::from("foo")), None);
+	assert!(FuzzyPath::from("/foo/bar").starts_with(&FuzzyPath::from("/foo")));
+	assert_eq!(FuzzyPath::from("\\\\?\\C:\\foo\\bar").strip_prefix(&FuzzyPath::from("foo")), None);
+}
+
+#[test]
+fn test_fuzzy_str_borrow() {
+	use std::collections::HashMap;
+
+	let mut map: HashMap<FuzzyPath, u32> = HashMap::new();
+	map.insert(FuzzyPath::from("Foo/Bar"), 1);
+
+	let lookup = FuzzyPath::from("foo/bar");
+	let fuzzy_str: &FuzzyStr = &lookup;
+	assert_eq!(map.get(fuzzy_str), Some(&1));
+
+	// These methods live on `FuzzyStr` (not just `FuzzyPath`), so a borrowed key pulled out of the
+	// map above can use them without allocating an owned `FuzzyPath`.
+	assert_eq!(fuzzy_str.components().collect::<Vec<_>>(), vec!["foo", "bar"]);
+	assert!(fuzzy_str.starts_with(unsafe { FuzzyStr::from_normalized_unchecked(b"foo") }));
+	assert_eq!(fuzzy_str.prefix(), FuzzyPathPrefix::None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_round_trip() {
+	let path = FuzzyPath::from("Foo/Bar");
+	let json = serde_json::to_string(&path).unwrap();
+	assert_eq!(json, "\"foo/bar\"");
+	assert_eq!(serde_json::from_str::<FuzzyPath>(&json).unwrap(), path);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_non_utf8_round_trip() {
+	use std::os::unix::ffi::OsStrExt;
+
+	let non_utf8 = OsStr::from_bytes(b"/foo/\xffbar");
+	let fuzzy = FuzzyPath::from(non_utf8);
+	assert_eq!(fuzzy.as_bytes(), b"/foo/\xffbar");
+	assert!(FuzzyPath::from_utf8(non_utf8).is_err());
+}
+
+#[test]
+fn test_windows_prefixes() {
+	assert_eq!(FuzzyPath::from("\\\\?\\C:\\foo").prefix(), FuzzyPathPrefix::Drive('c'));
+	assert_eq!(FuzzyPath::from("\\\\?\\C:\\foo"), FuzzyPath::from("C:\\foo"));
+	assert_eq!(FuzzyPath::from("c:\\foo"), FuzzyPath::from("C:/foo"));
+
+	assert_eq!(FuzzyPath::from("\\\\server\\share\\foo").prefix(), FuzzyPathPrefix::Unc);
+	assert_eq!(FuzzyPath::from("\\\\server\\share\\foo"), FuzzyPath::from("//SERVER/SHARE/foo"));
+	assert_eq!(FuzzyPath::from("\\\\?\\UNC\\server\\share\\foo"), FuzzyPath::from("//server/share/foo"));
+
+	assert_eq!(FuzzyPath::from("\\\\.\\pipe\\foo").prefix(), FuzzyPathPrefix::Device);
+	assert_eq!(FuzzyPath::from("\\\\.\\pipe\\foo"), FuzzyPath::from("//./pipe/foo"));
+
+	// A UNC server name that itself starts with `.` is still a UNC path, not a device path - `prefix()`
+	// must agree with `parse_windows_prefix`, which only recognizes `//.` as a device prefix when a
+	// separator immediately follows the dot.
+	assert_eq!(FuzzyPath::from("\\\\.hiddenserver\\share").prefix(), FuzzyPathPrefix::Unc);
+
+	assert_eq!(FuzzyPath::from("foo/bar").prefix(), FuzzyPathPrefix::None);
+}
+
+#[test]
+fn test_drive_relative_paths_are_not_absolute() {
+	// `C:foo` is drive-relative (relative to drive C's current directory), distinct from the
+	// absolute `C:\foo` - they must not normalize to the same path.
+	assert_ne!(FuzzyPath::from("C:foo"), FuzzyPath::from("C:\\foo"));
+	assert_eq!(FuzzyPath::from("C:foo"), FuzzyPath::from("c:foo"));
+}
@@ -2,6 +2,15 @@ use serde::{*, de::Visitor};
 
 use crate::FuzzyPath;
 
+impl Serialize for FuzzyPath {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer
+	{
+		serializer.serialize_str(&self.as_str_lossy())
+	}
+}
+
 struct FuzzyPathVisitor;
 impl<'v> Visitor<'v> for FuzzyPathVisitor {
 	type Value = FuzzyPath;